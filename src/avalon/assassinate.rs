@@ -5,19 +5,21 @@ use command_data_derive::CommandData;
 use discorsd::{async_trait, BotState};
 use discorsd::commands::*;
 use discorsd::errors::BotError;
-use discorsd::http::channel::embed;
-use discorsd::model::ids::UserId;
+use discorsd::http::channel::{embed, RichEmbed};
+use discorsd::model::ids::{GuildId, Id, UserId};
+use discorsd::model::interaction::MenuSelectData;
 use discorsd::model::interaction_response::message;
 use discorsd::model::message::Color;
 use discorsd::model::user::UserMarkup;
 
-use crate::avalon::characters::Character::Merlin;
+use crate::avalon::characters::Character::{Assassin, Merlin};
 use crate::avalon::characters::Loyalty::Evil;
+use crate::avalon::game::{AvalonGame, AvalonState};
 use crate::Bot;
 use crate::error::GameError;
 
 #[derive(Clone, Debug)]
-pub struct AssassinateCommand(pub UserId);
+pub struct AssassinateCommand;
 
 #[async_trait]
 impl SlashCommand for AssassinateCommand {
@@ -39,56 +41,24 @@ impl SlashCommand for AssassinateCommand {
                  interaction: InteractionUse<AppCommandData, Unused>,
                  data: AssassinateData,
     ) -> Result<InteractionUse<AppCommandData, Used>, BotError<GameError>> {
-        let result = if interaction.user().id == self.0 {
-            let target = data.target;
-            let guild = interaction.guild().unwrap();
-            let mut guard = state.bot.avalon_games.write().await;
-            let avalon = guard.get_mut(&guild).unwrap();
-            let game = avalon.game_mut();
-            match game.player_ref(target) {
-                None => {
-                    interaction.respond(&state.client, message(|m| {
-                        m.content(format!("{} is not playing Avalon", target.ping()));
-                        m.ephemeral();
-                    })).await
-                }
-                Some(evil) if evil.role.loyalty() == Evil => {
-                    interaction.respond(&state.client, message(|m| {
-                        m.content(format!("{} is evil, you can't assassinate them!", target.ping()));
-                        m.ephemeral();
-                    })).await
-                }
-                Some(guess) => {
-                    let interaction = interaction.delete(&state).await?;
-                    let game_over = embed(|e| {
-                        if guess.role == Merlin {
-                            e.color(Color::RED);
-                            e.title(format!("Correct! {} was Merlin! The bad guys win!", guess.member.nick_or_name()));
-                        } else {
-                            let merlin = game.players.iter().find(|p| p.role == Merlin).unwrap();
-                            e.color(Color::BLUE);
-                            e.title(format!(
-                                "Incorrect! {} was actually {}, and {} was Merlin! The good guys win!",
-                                guess.member.nick_or_name(),
-                                guess.role,
-                                merlin.member.nick_or_name(),
-                            ))
-                        }
-                    });
-                    let guard = state.slash_commands.read().await;
-                    let commands = guard.get(&guild).unwrap()
-                        .write().await;
-                    avalon.game_over(&*state, guild, commands, game_over).await?;
-                    Ok(interaction)
-                }
-            }
-        } else {
-            interaction.respond(&state.client, message(|m| {
-                m.content(format!("Only the assassin ({}) can assassinate someone", self.0.ping()));
+        let guild = interaction.guild().unwrap();
+        let mut guard = state.bot.avalon_games.write().await;
+        let avalon = guard.get_mut(&guild).unwrap();
+        let game = avalon.game_mut();
+        if let Some(error) = game.assassinate_error(interaction.user().id, data.target) {
+            return interaction.respond(&state.client, message(|m| {
+                m.content(error);
                 m.ephemeral();
-            })).await
-        };
-        result.map_err(|e| e.into())
+            })).await.map_err(Into::into);
+        }
+
+        let interaction = interaction.delete(&state).await?;
+        let game_over = game.assassination(data.target);
+        let guard = state.slash_commands.read().await;
+        let commands = guard.get(&guild).unwrap()
+            .write().await;
+        avalon.game_over(&*state, guild, commands, game_over).await?;
+        Ok(interaction)
     }
 }
 
@@ -96,4 +66,91 @@ impl SlashCommand for AssassinateCommand {
 pub struct AssassinateData {
     #[command(desc = "Your guess of who is Merlin")]
     target: UserId,
-}
\ No newline at end of file
+}
+
+/// Sent to the Assassin once the good guys have succeeded three quests
+#[derive(Clone, Debug)]
+pub struct AssassinateMenu(pub GuildId);
+
+#[async_trait]
+impl MenuCommand for AssassinateMenu {
+    type Bot = Bot;
+    type Data = UserId;
+
+    async fn run(
+        &self,
+        state: Arc<BotState<Self::Bot>>,
+        interaction: InteractionUse<MenuSelectData, Unused>,
+        mut data: Vec<Self::Data>,
+    ) -> Result<InteractionUse<MenuSelectData, Used>, BotError<GameError>> {
+        let guild = self.0;
+        let target = data.remove(0);
+        let mut guard = state.bot.avalon_games.write().await;
+        let Some(avalon) = guard.get_mut(&guild).filter(|a| a.try_game_ref().is_some()) else {
+            return interaction.respond(&state, message(|m| {
+                m.content("This game of Avalon is over");
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        };
+        let game = avalon.game_mut();
+        if let Some(error) = game.assassinate_error(interaction.user().id, target) {
+            return interaction.respond(&state, message(|m| {
+                m.content(error);
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        }
+
+        let target_name = game.player_ref(target).expect("target is playing").member.nick_or_name();
+        let interaction = interaction.update(&state, embed(|e| {
+            e.title(format!("The Assassin has chosen to assassinate {}", target_name));
+        })).await?;
+        let game_over = game.assassination(target);
+        let guard = state.slash_commands.read().await;
+        let commands = guard.get(&guild).unwrap()
+            .write().await;
+        avalon.game_over(&state, guild, commands, game_over).await?;
+        Ok(interaction)
+    }
+}
+
+impl AvalonGame {
+    /// Why `assassin` can't assassinate `target`, if they can't
+    pub fn assassinate_error(&self, assassin: UserId, target: UserId) -> Option<String> {
+        if !matches!(self.state, AvalonState::Assassinate) {
+            return Some("It isn't time to assassinate Merlin".into());
+        }
+        match self.players.iter().find(|p| p.role == Assassin) {
+            Some(p) if p.id() == assassin => {}
+            Some(p) => return Some(format!("Only the assassin ({}) can assassinate someone", p.ping())),
+            None => return Some("There is no assassin in this game".into()),
+        }
+        match self.player_ref(target) {
+            None => Some(format!("{} is not playing Avalon", target.ping())),
+            Some(evil) if evil.role.loyalty() == Evil => {
+                Some(format!("{} is evil, you can't assassinate them!", target.ping()))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// The game over message for assassinating `target`. Check
+    /// [`assassinate_error`](Self::assassinate_error) first.
+    pub fn assassination(&self, target: UserId) -> RichEmbed {
+        let guess = self.player_ref(target).expect("target is playing");
+        embed(|e| {
+            if guess.role == Merlin {
+                e.color(Color::RED);
+                e.title(format!("Correct! {} was Merlin! The bad guys win!", guess.member.nick_or_name()));
+            } else {
+                let merlin = self.players.iter().find(|p| p.role == Merlin).unwrap();
+                e.color(Color::BLUE);
+                e.title(format!(
+                    "Incorrect! {} was actually {}, and {} was Merlin! The good guys win!",
+                    guess.member.nick_or_name(),
+                    guess.role,
+                    merlin.member.nick_or_name(),
+                ))
+            }
+        })
+    }
+}
@@ -26,10 +26,14 @@ impl Debug for Board {
 }
 
 impl Board {
-    pub fn new(players: usize) -> Self {
+    /// The board for `players` players, if there is one (only 5 to 10 players have a board)
+    pub fn new(players: usize) -> Option<Self> {
+        if !(5..=10).contains(&players) {
+            return None;
+        }
         let board = Reader::open(format!("images/avalon/board/{}.jpg", players))
             .unwrap().decode().unwrap();
-        Self(players, board)
+        Some(Self(players, board))
     }
 
     pub fn image(&self, wins: &[bool], rejects: usize) -> Option<Vec<u8>> {
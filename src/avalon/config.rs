@@ -12,11 +12,12 @@ use discorsd::model::ids::*;
 use discorsd::model::message::Message;
 use discorsd::model::user::UserMarkup;
 
-use crate::{avalon, Bot};
+use crate::Bot;
 use crate::avalon::characters::Character;
 use crate::avalon::characters::Loyalty::Evil;
 use crate::avalon::lotl::ToggleLady;
 use crate::avalon::roles::RolesCommand;
+use crate::avalon::rounds::{HouseRulesCommand, QuestRules};
 use crate::avalon::SlashCommandRaw;
 use crate::commands::addme::AddMeCommand;
 use crate::commands::start::StartCommand;
@@ -27,6 +28,8 @@ pub struct AvalonConfig {
     pub players: Vec<GuildMember>,
     pub roles: Vec<Character>,
     pub lotl: bool,
+    /// how many players can play, and the evil players and quests for each player count
+    pub rules: QuestRules,
 
     /// the interaction whose message is being edited to show the game settings
     pub message: Option<Message>,
@@ -34,16 +37,27 @@ pub struct AvalonConfig {
 
 impl AvalonConfig {
     pub fn startable(&self) -> bool {
-        let max_evil = self.max_evil();
+        self.start_error().is_none()
+    }
+
+    /// Why Avalon can't be started with these settings, if it can't
+    pub fn start_error(&self) -> Option<String> {
         let num_evil = self.roles.iter()
             .filter(|r| r.loyalty() == Evil)
             .count();
-        self.players.len() >= self.roles.len() &&
-            match max_evil {
-                None => false,
-                Some(max_evil) if num_evil > max_evil => false,
-                Some(_) => true,
+        match self.max_evil() {
+            None => Some(format!(
+                "There are no rules for {} players, use `/house-rules` to add them",
+                self.players.len()
+            )),
+            Some(_) if self.players.len() < self.roles.len() => {
+                Some("There are more roles than players".into())
+            }
+            Some(max_evil) if num_evil > max_evil => {
+                Some(format!("There can only be {} evil roles with {} players", max_evil, self.players.len()))
             }
+            Some(_) => None,
+        }
     }
 
     pub fn embed(&self) -> RichEmbed {
@@ -76,21 +90,28 @@ impl AvalonConfig {
                     roles.push_str(&format!("\n{}x Minion of Mordred", mom))
                 }
             };
-            match self.max_evil() {
-                None if self.players.len() < 5 => {
-                    // assume that there will be 5 players, so treat max_evil as 2
-                    let max_evil = 2;
-                    fill(5, max_evil)
+            match (self.max_evil(), self.rules.min_players()) {
+                (None, Some(min_players)) if self.players.len() < min_players => {
+                    // assume that there will be the fewest players allowed
+                    fill(min_players, self.rules.max_evil(min_players).unwrap())
                 }
-                Some(max_evil) => {
+                (Some(max_evil), _) => {
                     fill(self.players.len(), max_evil)
                 }
-                None => {
-                    // AvalonError::TooManyPlayers(self.players.len())?
+                (None, _) => {
+                    roles.push_str(&format!("\nThere are no rules for {} players", self.players.len()));
                 }
             }
             e.add_inline_field("Roles", roles);
             e.add_inline_field("Lady of the Lake", if self.lotl { "enabled" } else { "disabled" });
+            let standard = QuestRules::default();
+            let house_rules = self.rules.iter()
+                .filter(|&(players, rules)| standard.get(players) != Some(rules))
+                .map(|(players, rules)| format!("{} players ({} evil):\n{}", players, rules.evil, rules.rounds))
+                .join("\n");
+            if !house_rules.is_empty() {
+                e.add_field("House Rules", house_rules);
+            }
         })
     }
 
@@ -137,13 +158,14 @@ impl AvalonConfig {
     }
 
     pub fn max_evil(&self) -> Option<usize> {
-        avalon::max_evil(self.players.len())
+        self.rules.max_evil(self.players.len())
     }
 
     pub fn is_setup_command(command: &dyn SlashCommandRaw<Bot=Bot>) -> bool {
         command.is::<StartCommand>() ||
             command.is::<AddMeCommand>() ||
             command.is::<RolesCommand>() ||
-            command.is::<ToggleLady>()
+            command.is::<ToggleLady>() ||
+            command.is::<HouseRulesCommand>()
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use discorsd::{BotState, GuildCommands};
 use discorsd::commands::*;
@@ -7,9 +8,12 @@ use discorsd::http::ClientResult;
 use discorsd::model::ids::*;
 use discorsd::model::message::{ChannelMessageId, Color};
 use discorsd::model::user::UserMarkup;
+use itertools::Itertools;
 use tokio::sync::RwLockWriteGuard;
 
+use crate::avalon::assassinate::AssassinateMenu;
 use crate::avalon::board::Board;
+use crate::avalon::lotl::LotlMenu;
 use crate::Bot;
 use crate::commands::stop::StopVoteCommand;
 
@@ -17,18 +21,24 @@ use super::{
     Avalon,
     AvalonPlayer,
     characters::{Character::{Assassin, Merlin}, Loyalty::Evil},
-    rounds::{Round, Rounds},
-    vote::{PartyVote, QuestVote},
+    rounds::{PlayerCountRules, Round},
+    vote::{party_vote_results, PartyVote, quest_vote_results, QuestVote, vote_checker},
 };
 
 #[derive(Debug, Clone)]
 pub struct AvalonGame {
+    /// Random, so that buttons and vote checkers from an earlier game aren't mistaken for this one's
+    pub id: u64,
     pub state: AvalonState,
     pub channel: ChannelId,
+    /// Whoever started the game, who can undo votes along with the server owner
+    pub host: UserId,
     pub players: Vec<AvalonPlayer>,
     // pub roles: AvalonRoles,
-    pub rounds: Rounds,
-    pub board: Board,
+    pub rules: PlayerCountRules,
+    /// `None` if there is no board image for this many players. Shared so that undo snapshots
+    /// don't copy the image.
+    pub board: Option<Arc<Board>>,
     pub lotl: Option<usize>,
     pub leader: usize,
     pub round: usize,
@@ -37,20 +47,27 @@ pub struct AvalonGame {
     pub prev_ladies: Vec<UserId>,
     pub pins: HashSet<ChannelMessageId>,
     pub stop_votes: (i8, i8),
+    /// The game as it was right before the most recent vote was tallied
+    pub undo: Option<Box<Self>>,
+    /// How many votes have been tallied, so that an Undo button only undoes its own vote
+    pub tallies: usize,
 }
 
 impl AvalonGame {
     pub fn new(channel: ChannelId,
+               host: UserId,
                players: Vec<AvalonPlayer>,
                lotl: Option<usize>,
+               rules: PlayerCountRules,
     ) -> Self {
-        let rounds = Rounds(players.len());
-        let board = Board::new(players.len());
+        let board = Board::new(players.len()).map(Arc::new);
         Self {
+            id: rand::random(),
             state: AvalonState::GameStart,
             channel,
+            host,
             players,
-            rounds,
+            rules,
             board,
             lotl,
             leader: 0,
@@ -60,9 +77,74 @@ impl AvalonGame {
             prev_ladies: Vec::new(),
             pins: Default::default(),
             stop_votes: (0, 0),
+            undo: None,
+            tallies: 0,
         }
     }
 
+    /// Save the current state so that it can be restored by [`undo`](Self::undo). Only one step is
+    /// kept, so this overwrites any earlier snapshot.
+    pub fn snapshot(&mut self) {
+        self.tallies += 1;
+        let mut snapshot = self.clone();
+        snapshot.undo = None;
+        self.undo = Some(Box::new(snapshot));
+    }
+
+    /// The most recent tally of this game
+    pub fn tally_id(&self) -> TallyId {
+        TallyId { game: self.id, tally: self.tallies }
+    }
+
+    /// Roll back to the snapshot taken for `tally`, re-registering the vote's reaction command and
+    /// vote checker so that players can change their vote. Returns `false` if there is nothing to
+    /// undo, or if `tally` isn't this game's most recent tally.
+    pub async fn undo(&mut self, state: &Arc<BotState<Bot>>, guild: GuildId, tally: TallyId) -> bool {
+        if self.tally_id() != tally {
+            return false;
+        }
+        let Some(snapshot) = self.undo.take() else { return false };
+        *self = *snapshot;
+
+        let mut reaction_commands = state.reaction_commands.write().await;
+        reaction_commands.retain(|rc|
+            !matches!(rc.downcast_ref::<PartyVote>(), Some(pv) if pv.guild == guild) &&
+                !matches!(rc.downcast_ref::<QuestVote>(), Some(qv) if qv.guild == guild)
+        );
+        match &self.state {
+            AvalonState::PartyVote(votes, _) => {
+                reaction_commands.push(Box::new(PartyVote {
+                    guild,
+                    messages: votes.keys().copied().collect(),
+                }));
+                tokio::spawn(vote_checker(
+                    Arc::clone(state),
+                    guild,
+                    tally,
+                    [PartyVote::APPROVE, PartyVote::REJECT],
+                    AvalonState::party_vote_mut,
+                    party_vote_results,
+                ));
+            }
+            AvalonState::Questing(votes) => {
+                reaction_commands.push(Box::new(QuestVote {
+                    guild,
+                    messages: votes.keys().copied().collect(),
+                }));
+                tokio::spawn(vote_checker(
+                    Arc::clone(state),
+                    guild,
+                    tally,
+                    [QuestVote::SUCCEED, QuestVote::FAIL],
+                    AvalonState::questing_vote_mut,
+                    quest_vote_results,
+                ));
+            }
+            _ => {}
+        }
+        true
+    }
+
     // has to be an associated fn because of &mut rules
     pub fn advance_leader(leader: &mut usize, num_players: usize) {
         *leader += 1;
@@ -84,11 +166,12 @@ impl AvalonGame {
     }
 
     pub fn round(&self) -> Round {
-        self.rounds[self.round]
+        self.rules.rounds[self.round]
     }
 
     pub fn board_image(&self) -> Option<(&'static str, Vec<u8>)> {
-        self.board.image(&self.good_won, self.rejected_quests)
+        self.board.as_deref()?
+            .image(&self.good_won, self.rejected_quests)
             .map(|image| ("board.jpg", image))
     }
 
@@ -116,7 +199,7 @@ impl Avalon {
                     m.content(assassin.ping());
                     m.embed(|e| {
                         e.title("The good guys have succeeded three quests, but the Assassin can still try to kill Merlin");
-                        e.description("Use `/assassinate` to assassinate who you think is Merlin");
+                        e.description("Choose who you think is Merlin below to assassinate them");
                         e.fields(
                             game.players
                                 .iter()
@@ -128,6 +211,7 @@ impl Avalon {
                                 ))
                         );
                     });
+                    m.menu(&state, AssassinateMenu(guild), |m| m.placeholder("Choose who to assassinate..."));
                 })).await?;
                 // let (assassinate_id, assassinate) = state.get_command_mut::<AssassinateCommand>(guild, &mut commands).await;
                 // assassinate_id.allow_users(&state, guild, &[assassin.id()]).await?;
@@ -155,10 +239,17 @@ impl Avalon {
                         lotl.member.nick_or_name()
                     ));
                     e.description(
-                        "Use `/lotl` to find someone's alignment. You can't use this on \
-                                        someone who has already had the Lady of the Lake."
+                        "Choose someone below to privately learn their alignment. You can't use \
+                        this on someone who has already had the Lady of the Lake."
+                    );
+                    e.add_field(
+                        "Can be chosen",
+                        game.lady_targets()
+                            .map(UserMarkup::ping)
+                            .join("\n"),
                     );
                 });
+                m.menu(&state, LotlMenu(guild), |m| m.placeholder("Choose a player..."));
             })).await?;
             // let (lotl_id, lotl_command) = state.get_command_mut::<LotlCommand>(guild, &mut commands).await;
             // lotl_command.0 = lotl.id();
@@ -216,6 +307,14 @@ impl AvalonGame {
     }
 }
 
+/// Identifies one tally of one game, so that an Undo button or vote checker from an earlier vote
+/// can tell that it's out of date
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TallyId {
+    game: u64,
+    tally: usize,
+}
+
 #[derive(Debug, Clone)]
 pub enum AvalonState {
     GameStart,
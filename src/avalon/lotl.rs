@@ -5,18 +5,21 @@ use command_data_derive::CommandData;
 use discorsd::{async_trait, BotState};
 use discorsd::commands::*;
 use discorsd::errors::BotError;
-use discorsd::http::channel::create_message;
+use discorsd::http::channel::{create_message, embed};
+use discorsd::http::ClientResult;
 use discorsd::http::user::UserExt;
-use discorsd::model::ids::{Id, UserId};
+use discorsd::model::ids::{GuildId, Id, UserId};
+use discorsd::model::interaction::MenuSelectData;
 use discorsd::model::interaction_response::message;
 use discorsd::model::user::UserMarkup;
 
-use crate::avalon::game::AvalonGame;
+use crate::avalon::{Avalon, AvalonPlayer};
+use crate::avalon::game::{AvalonGame, AvalonState};
 use crate::Bot;
 use crate::error::GameError;
 
 #[derive(Clone, Debug)]
-pub struct LotlCommand(pub UserId);
+pub struct LotlCommand;
 
 #[allow(clippy::use_self)]
 #[async_trait]
@@ -39,74 +42,137 @@ impl SlashCommand for LotlCommand {
                  interaction: InteractionUse<AppCommandData, Unused>,
                  data: LadyData,
     ) -> Result<InteractionUse<AppCommandData, Used>, BotError<GameError>> {
-        let result = if interaction.user().id == self.0 {
-            let guild = interaction.guild().unwrap();
-            let target = data.target;
-            let mut guard = state.bot.avalon_games.write().await;
-            let game = guard.get_mut(&guild).unwrap().game_mut();
-            match game.player_ref(target).cloned() {
-                None => {
-                    interaction.respond(&state.client, message(|m| {
-                        m.content(format!("{} is not playing Avalon", target.ping()));
-                        m.ephemeral();
-                    })).await
-                }
-                Some(target) if target.id() == self.0 => {
-                    interaction.respond(&state.client, message(|m| {
-                        m.content("You can't use the Lady of the Lake on yourself");
-                        m.ephemeral();
-                    })).await
-                }
-                Some(target) => {
-                    if let Some(idx) = game.prev_ladies.iter().position(|id| *id == target.id()) {
-                        interaction.respond(&state.client, message(|m| {
-                            m.content(format!(
-                                "You can't use the Lady of the Lake on someone who had the Lady of the \
-                                Lake in the past. {} had the Lady of the Lake {}.",
-                                target.ping(),
-                                match idx {
-                                    0 => "first",
-                                    1 => "second",
-                                    2 => "third? that seems unlikely. plz tell Andrew this happened lol",
-                                    _ => unreachable!("harumph"),
-                                }
-                            ));
-                            m.ephemeral();
-                        })).await
-                    } else {
-                        self.0.send_dm(&*state, create_message(|m| {
-                            m.content(format!("{} is {}", target.ping(), target.role.loyalty()));
-                            m.attachment(target.role.loyalty().image());
-                        })).await?;
-                        let target_idx = game.players.iter()
-                            .position(|p| p.id() == target.id())
-                            .unwrap();
-                        // state.command_id::<LotlCommand>(guild).await
-                        //     .disallow_users(&state, guild, &[self.0]).await?;
-
-                        game.lotl = Some(target_idx);
-                        game.prev_ladies.push(self.0);
-                        game.round += 1;
-                        AvalonGame::advance_leader(&mut game.leader, game.players.len());
-
-                        let guard = state.slash_commands.read().await;
-                        let commands = guard.get(&guild).unwrap()
-                            .write().await;
-                        game.start_round(&*state, guild, commands).await?;
-                        interaction.delete(&state).await
-                    }
-                }
-            }
-        } else {
+        let guild = interaction.guild().unwrap();
+        let mut guard = state.bot.avalon_games.write().await;
+        let game = guard.get_mut(&guild).unwrap().game_mut();
+        let result = if let Some(error) = game.lady_error(interaction.user().id, data.target) {
             interaction.respond(&state.client, message(|m| {
-                m.content(format!("Only {} can use the Lady of the Lake", self.0.ping()));
+                m.content(error);
                 m.ephemeral();
             })).await
+        } else {
+            game.use_lady(&state, guild, data.target).await?;
+            interaction.delete(&state).await
         };
         result.map_err(|e| e.into())
     }
 }
 
+/// Sent to the holder of the Lady of the Lake after the 2nd, 3rd, and 4th quests
+#[derive(Clone, Debug)]
+pub struct LotlMenu(pub GuildId);
+
+#[async_trait]
+impl MenuCommand for LotlMenu {
+    type Bot = Bot;
+    type Data = UserId;
+
+    async fn run(
+        &self,
+        state: Arc<BotState<Self::Bot>>,
+        interaction: InteractionUse<MenuSelectData, Unused>,
+        mut data: Vec<Self::Data>,
+    ) -> Result<InteractionUse<MenuSelectData, Used>, BotError<GameError>> {
+        let guild = self.0;
+        let holder = interaction.user().id;
+        let target = data.remove(0);
+        let mut guard = state.bot.avalon_games.write().await;
+        let Some(game) = guard.get_mut(&guild).and_then(Avalon::try_game_mut) else {
+            return interaction.respond(&state, message(|m| {
+                m.content("This game of Avalon is over");
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        };
+        if let Some(error) = game.lady_error(holder, target) {
+            return interaction.respond(&state, message(|m| {
+                m.content(error);
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        }
+
+        let title = format!(
+            "{} used the Lady of the Lake",
+            game.player_ref(holder).expect("holder is playing").member.nick_or_name(),
+        );
+        game.use_lady(&state, guild, target).await?;
+        interaction.update(&state, embed(|e| {
+            e.title(title);
+            e.description(format!("{} now has the Lady of the Lake", target.ping()));
+        })).await.map_err(Into::into)
+    }
+}
+
+impl AvalonGame {
+    /// The players the current holder of the Lady of the Lake is allowed to use it on
+    pub fn lady_targets(&self) -> impl Iterator<Item=&AvalonPlayer> {
+        let holder = self.lotl().map(AvalonPlayer::id);
+        self.players.iter()
+            .filter(move |p| Some(p.id()) != holder && !self.prev_ladies.contains(&p.id()))
+    }
+
+    /// Why `holder` can't use the Lady of the Lake on `target`, if they can't
+    pub fn lady_error(&self, holder: UserId, target: UserId) -> Option<String> {
+        if !matches!(self.state, AvalonState::Lotl) {
+            return Some("It isn't time to use the Lady of the Lake".into());
+        }
+        match self.lotl() {
+            Some(lotl) if lotl.id() == holder => {}
+            Some(lotl) => return Some(format!("Only {} can use the Lady of the Lake", lotl.ping())),
+            None => return Some("The Lady of the Lake isn't being used this game".into()),
+        }
+        if self.player_ref(target).is_none() {
+            Some(format!("{} is not playing Avalon", target.ping()))
+        } else if target == holder {
+            Some("You can't use the Lady of the Lake on yourself".into())
+        } else if let Some(idx) = self.prev_ladies.iter().position(|id| *id == target) {
+            Some(format!(
+                "You can't use the Lady of the Lake on someone who had the Lady of the \
+                Lake in the past. {} had the Lady of the Lake {}.",
+                target.ping(),
+                match idx {
+                    0 => "first",
+                    1 => "second",
+                    2 => "third? that seems unlikely. plz tell Andrew this happened lol",
+                    _ => unreachable!("harumph"),
+                }
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Privately reveal `target`'s loyalty to the holder of the Lady of the Lake, pass the Lady to
+    /// `target`, and start the next round. Check [`lady_error`](Self::lady_error) first.
+    pub async fn use_lady(
+        &mut self,
+        state: &BotState<Bot>,
+        guild: GuildId,
+        target: UserId,
+    ) -> ClientResult<()> {
+        let holder = self.lotl().expect("Lady of the Lake is in use").id();
+        let target_idx = self.players.iter()
+            .position(|p| p.id() == target)
+            .expect("target is playing");
+        let loyalty = self.players[target_idx].role.loyalty();
+        holder.send_dm(state, create_message(|m| {
+            m.content(format!("{} is {}", target.ping(), loyalty));
+            m.attachment(loyalty.image());
+        })).await?;
+
+        self.undo = None;
+        self.lotl = Some(target_idx);
+        self.prev_ladies.push(holder);
+        self.round += 1;
+        Self::advance_leader(&mut self.leader, self.players.len());
+        self.state = AvalonState::RoundStart;
+
+        let guard = state.slash_commands.read().await;
+        let commands = guard.get(&guild).unwrap()
+            .write().await;
+        self.start_round(state, guild, commands).await
+    }
+}
+
 #[derive(CommandData, Debug)]
 pub struct LadyData {
     #[command(desc = "The player whose alignment you want to see.")]
@@ -31,17 +31,19 @@ pub mod game;
 pub mod board;
 pub mod start;
 pub mod setup;
+pub mod undo;
 
 pub fn commands() -> Vec<Box<dyn SlashCommandRaw<Bot=Bot>>> {
     vec![
         Box::new(roles::RolesCommand(Vec::new())),
         Box::new(vote::VoteStatus),
         Box::new(lotl::ToggleLady),
-        // these just filter based on id, so no bad requests or anything can be caused by this,
-        // and they are disabled to everyone by default
-        Box::new(assassinate::AssassinateCommand(UserId(0))),
-        Box::new(lotl::LotlCommand(UserId(0))),
-        // similarly, just means no people can be sent on quest
+        Box::new(rounds::HouseRulesCommand),
+        // only the holder of the Lady of the Lake or the assassin can use these, and they are
+        // disabled to everyone by default
+        Box::new(lotl::LotlCommand),
+        Box::new(assassinate::AssassinateCommand),
+        // just means no people can be sent on quest
         Box::new(quest::QuestCommand(0)),
     ]
 }
@@ -92,10 +94,11 @@ impl Avalon {
         self.try_game_ref().expect("expected Avalon to be in the Game state")
     }
 
-    pub fn start(&mut self, channel: ChannelId) -> &mut AvalonGame {
+    pub fn start(&mut self, channel: ChannelId, host: UserId) -> &mut AvalonGame {
         let config = std::mem::take(self.config_mut());
-        let max_evil = config.max_evil().unwrap();
-        let AvalonConfig { mut players, mut roles, lotl, .. } = config;
+        let AvalonConfig { mut players, mut roles, lotl, rules, .. } = config;
+        let rules = *rules.get(players.len()).expect("checked by `AvalonConfig::start_error`");
+        let max_evil = rules.evil;
 
         let num_evil = roles.iter()
             .filter(|c| c.loyalty() == Evil)
@@ -115,7 +118,7 @@ impl Avalon {
             .collect_vec();
         let lotl = if lotl { Some(players.len() - 1) } else { None };
 
-        *self = Self::Game(AvalonGame::new(channel, players, lotl));
+        *self = Self::Game(AvalonGame::new(channel, host, players, lotl, rules));
         self.game_mut()
     }
 }
@@ -206,12 +209,3 @@ impl Avalon {
         Ok(())
     }
 }
-
-pub fn max_evil(num_players: usize) -> Option<usize> {
-    match num_players {
-        5..=6 => Some(2),
-        7..=9 => Some(3),
-        10 => Some(4),
-        _ => None,
-    }
-}
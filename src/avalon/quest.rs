@@ -123,6 +123,7 @@ impl SlashCommand for QuestCommand {
                         tokio::spawn(crate::avalon::vote::vote_checker(
                             Arc::clone(&state),
                             guild,
+                            game.tally_id(),
                             [PartyVote::APPROVE, PartyVote::REJECT],
                             AvalonState::party_vote_mut,
                             crate::avalon::vote::party_vote_results,
@@ -131,6 +132,8 @@ impl SlashCommand for QuestCommand {
                         // state.enable_command::<VoteStatus>(guild).await?;
                         // state.command_id::<QuestCommand>(guild).await
                         //     .disallow_users(&state, guild, &[leader.id()]).await?;
+                        // a new party was proposed, so the last vote can't be undone anymore
+                        game.undo = None;
                         game.state = AvalonState::PartyVote(votes, party);
                     }
                     result
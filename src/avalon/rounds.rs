@@ -1,19 +1,25 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::ops::Index;
+use std::sync::Arc;
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub struct Rounds(pub usize);
+use command_data_derive::CommandData;
+use discorsd::{async_trait, BotState};
+use discorsd::commands::*;
+use discorsd::errors::BotError;
+use discorsd::model::interaction_response::message;
 
-impl Rounds {
-    pub fn rounds(self) -> [Round; 5] {
-        MAP[self.0.saturating_sub(5)]
-    }
-}
+use crate::avalon::Avalon;
+use crate::Bot;
+use crate::error::{AvalonError, GameError};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Rounds(pub [Round; 5]);
 
 impl Display for Rounds {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !(5..=10).contains(&self.0) { return Ok(()) }
-        self.rounds().iter()
+        self.0.iter()
             .zip(1..=5)
             .try_for_each(|(round, i)| {
                 write!(f,
@@ -41,7 +47,7 @@ impl Index<usize> for Rounds {
 
     // 1 indexed :)
     fn index(&self, index: usize) -> &Self::Output {
-        &MAP[self.0.saturating_sub(5)][index - 1]
+        &self.0[index - 1]
     }
 }
 
@@ -51,13 +57,165 @@ pub struct Round {
     pub fails: usize,
 }
 
+/// How many players are evil and what the quests are for one number of players
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct PlayerCountRules {
+    pub evil: usize,
+    pub rounds: Rounds,
+}
+
+/// The player counts Avalon can be played with and the [`PlayerCountRules`] for each of them.
+///
+/// Defaults to the standard rules for 5 to 10 players, but house rules for other player counts
+/// can be added with [`insert`](Self::insert).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct QuestRules(BTreeMap<usize, PlayerCountRules>);
+
+impl Default for QuestRules {
+    fn default() -> Self {
+        Self(
+            (5..=10).zip(STANDARD_EVIL)
+                .zip(STANDARD_ROUNDS)
+                .map(|((players, evil), rounds)| (players, PlayerCountRules { evil, rounds: Rounds(rounds) }))
+                .collect()
+        )
+    }
+}
+
+impl QuestRules {
+    /// Add the rules for games with `players` players, replacing any rules already set for that
+    /// many players.
+    ///
+    /// # Errors
+    ///
+    /// If `players` isn't next to the player counts that already have rules (so that they stay a
+    /// range with no gaps), if there would not be more good players than evil players, or if any
+    /// quest sends more people than are playing or needs more fails than people on the quest.
+    pub fn insert(&mut self, players: usize, rules: PlayerCountRules) -> Result<(), AvalonError> {
+        let invalid = |reason| Err(AvalonError::InvalidRules(players, reason));
+        if let (Some(min), Some(max)) = (self.min_players(), self.max_players()) {
+            if players + 1 < min || players > max + 1 {
+                return invalid("rules can only be added for one more or one fewer player than already have rules");
+            }
+        }
+        if rules.evil == 0 {
+            return invalid("there must be at least 1 evil player");
+        }
+        if rules.evil * 2 >= players {
+            return invalid("there must be more good players than evil players");
+        }
+        for round in rules.rounds.0 {
+            if round.players == 0 || round.players > players {
+                return invalid("each quest must send between 1 and all of the players");
+            }
+            if round.fails == 0 || round.fails > round.players {
+                return invalid("each quest must need between 1 fail and a fail from everyone on it");
+            }
+        }
+        self.0.insert(players, rules);
+        Ok(())
+    }
+
+    pub fn get(&self, players: usize) -> Option<&PlayerCountRules> {
+        self.0.get(&players)
+    }
+
+    pub fn max_evil(&self, players: usize) -> Option<usize> {
+        self.get(players).map(|rules| rules.evil)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(usize, &PlayerCountRules)> {
+        self.0.iter().map(|(&players, rules)| (players, rules))
+    }
+
+    pub fn min_players(&self) -> Option<usize> {
+        self.0.keys().next().copied()
+    }
+
+    pub fn max_players(&self) -> Option<usize> {
+        self.0.keys().next_back().copied()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HouseRulesCommand;
+
+#[async_trait]
+impl SlashCommand for HouseRulesCommand {
+    type Bot = Bot;
+    type Data = HouseRulesData;
+    type Use = Used;
+    const NAME: &'static str = "house-rules";
+
+    fn description(&self) -> Cow<'static, str> {
+        "Set how many players are evil and go on each quest for a number of players".into()
+    }
+
+    async fn run(&self,
+                 state: Arc<BotState<Bot>>,
+                 interaction: InteractionUse<AppCommandData, Unused>,
+                 data: HouseRulesData,
+    ) -> Result<InteractionUse<AppCommandData, Used>, BotError<GameError>> {
+        let guild = interaction.guild().unwrap();
+        let mut guard = state.bot.avalon_games.write().await;
+        let Avalon::Config(config) = guard.entry(guild).or_default() else {
+            return interaction.respond(&state, message(|m| {
+                m.content("You can't change the rules during a game");
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        };
+        if let Err(error) = data.insert_into(&mut config.rules) {
+            return interaction.respond(&state, message(|m| {
+                m.content(error.to_string());
+                m.ephemeral();
+            })).await.map_err(Into::into);
+        }
+
+        let interaction = interaction.defer(&state).await?;
+        config.update_embed(&state, &interaction).await?;
+        interaction.delete(&state).await.map_err(Into::into)
+    }
+}
+
+#[derive(CommandData)]
+pub struct HouseRulesData {
+    #[command(desc = "The number of players these rules are for")]
+    players: i64,
+    #[command(desc = "How many of the players are evil")]
+    evil: i64,
+    #[command(desc = "How many players go on each quest, ex `2 3 2 3 3`. Add `*` if a quest needs 2 fails")]
+    quests: String,
+}
+
+impl HouseRulesData {
+    fn insert_into(self, rules: &mut QuestRules) -> Result<(), AvalonError> {
+        let players = usize::try_from(self.players)
+            .map_err(|_| AvalonError::InvalidPlayerCount(self.players))?;
+        let invalid = |reason| AvalonError::InvalidRules(players, reason);
+        let evil = usize::try_from(self.evil)
+            .map_err(|_| invalid("there must be at least 1 evil player"))?;
+        let rounds = self.quests
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|quest| !quest.is_empty())
+            .map(|quest| {
+                let (quest, fails) = quest.strip_suffix('*').map_or((quest, 1), |quest| (quest, 2));
+                quest.parse().map(|players| Round { players, fails })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .and_then(|rounds| <[Round; 5]>::try_from(rounds).ok())
+            .ok_or_else(|| invalid("there must be 5 quests, each a number of players"))?;
+        rules.insert(players, PlayerCountRules { evil, rounds: Rounds(rounds) })
+    }
+}
+
 macro_rules! r {
     ($players:expr, 2) => { Round { players: $players, fails: 2 } };
     ($players:expr) => { Round { players: $players, fails: 1 } };
 }
 
 // @formatter:off
-const MAP: [[Round; 5]; 6] = [
+const STANDARD_ROUNDS: [[Round; 5]; 6] = [
     // /* 5  */ [r!(2), r!(2), r!(2), r!(2),    r!(2)],
     /* 5  */ [r!(2), r!(3), r!(2), r!(3),    r!(3)],
     /* 6  */ [r!(2), r!(3), r!(4), r!(3),    r!(4)],
@@ -66,4 +224,13 @@ const MAP: [[Round; 5]; 6] = [
     /* 9  */ [r!(3), r!(4), r!(4), r!(5, 2), r!(5)],
     /* 10 */ [r!(3), r!(4), r!(4), r!(5, 2), r!(5)],
 ];
+
+const STANDARD_EVIL: [usize; 6] = [
+    /* 5  */ 2,
+    /* 6  */ 2,
+    /* 7  */ 3,
+    /* 8  */ 3,
+    /* 9  */ 3,
+    /* 10 */ 4,
+];
 // @formatter:on
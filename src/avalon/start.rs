@@ -8,6 +8,7 @@ use discorsd::BotState;
 use discorsd::commands::*;
 use discorsd::errors::BotError;
 use discorsd::http::channel::{embed, MessageChannelExt};
+use discorsd::http::interaction::webhook_message;
 use discorsd::http::ClientResult;
 use discorsd::http::user::UserExt;
 use discorsd::model::ids::Id;
@@ -17,8 +18,8 @@ use discorsd::model::user::UserMarkup;
 
 use crate::avalon::characters::{Character, Loyalty};
 use crate::avalon::characters::Character::{LoyalServant, MinionOfMordred};
+use crate::avalon::Avalon;
 use crate::avalon::game::AvalonGame;
-use crate::avalon::max_evil;
 use crate::Bot;
 use crate::error::GameError;
 
@@ -26,10 +27,21 @@ pub async fn start(state: &Arc<BotState<Bot>>, interaction: &InteractionUse<AppC
     let guild = interaction.guild().unwrap();
     let mut guard = state.bot.avalon_games.write().await;
     let avalon = guard.get_mut(&guild).unwrap();
-    let game = avalon.start(interaction.channel);
+    let error = match avalon {
+        Avalon::Config(config) => config.start_error(),
+        Avalon::Game(_) => Some("A game of Avalon is already in progress".into()),
+    };
+    if let Some(error) = error {
+        interaction.token.followup(state, webhook_message(|m| {
+            m.ephemeral();
+            m.content(error);
+        })).await?;
+        return Ok(());
+    }
+    let game = avalon.start(interaction.channel, interaction.user().id);
     state.client.trigger_typing(game.channel).await?;
     let board = game.board_image();
-    let AvalonGame { channel, players, lotl, .. } = game.clone();
+    let AvalonGame { channel, players, lotl, rules, .. } = game.clone();
 
     // send all of the players their roles
     let players = Arc::new(players);
@@ -112,7 +124,7 @@ pub async fn start(state: &Arc<BotState<Bot>>, interaction: &InteractionUse<AppC
         good.sort_by_key(|c| c.name());
         evil.sort_by_key(|c| c.name());
         let (n_good, n_evil) = (good.len(), evil.len());
-        let max_evil = max_evil(players.len()).unwrap();
+        let max_evil = rules.evil;
         let max_good = players.len() - max_evil;
         let mut roles = good.into_iter().map(Character::name).join("\n");
         let ls = max_good - n_good;
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use discorsd::{async_trait, BotState};
+use discorsd::commands::{ButtonCommand, InteractionUse, Unused, Used};
+use discorsd::errors::BotError;
+use discorsd::http::channel::embed;
+use discorsd::model::ids::GuildId;
+use discorsd::model::interaction::ButtonPressData;
+use discorsd::model::interaction_response::message;
+use discorsd::model::message::Color;
+use discorsd::model::user::UserMarkup;
+
+use crate::avalon::Avalon;
+use crate::avalon::game::{AvalonState, TallyId};
+use crate::Bot;
+use crate::error::GameError;
+
+/// Sent with the results of each vote, so that a misclick can be undone. Holds which tally it was
+/// sent with, so that only the most recent result can be undone.
+#[derive(Debug, Copy, Clone)]
+pub struct UndoButton(pub GuildId, pub TallyId);
+
+#[async_trait]
+impl ButtonCommand for UndoButton {
+    type Bot = Bot;
+
+    async fn run(
+        &self,
+        state: Arc<BotState<Self::Bot>>,
+        interaction: InteractionUse<ButtonPressData, Unused>,
+    ) -> Result<InteractionUse<ButtonPressData, Used>, BotError<GameError>> {
+        let Self(guild, tally) = *self;
+        let user = interaction.user().id;
+        let mut guard = state.bot.avalon_games.write().await;
+        let Some(game) = guard.get_mut(&guild).and_then(Avalon::try_game_mut) else {
+            return interaction.respond(&state, message(|m| {
+                m.ephemeral();
+                m.content("There is no game of Avalon to undo");
+            })).await.map_err(Into::into);
+        };
+
+        // whoever started the game, the server owner, or the bot's owner can undo
+        let is_host = user == game.host ||
+            user == state.bot.config.owner ||
+            state.cache.guild(guild).await.is_some_and(|g| g.owner_id == user);
+        if !is_host {
+            return interaction.respond(&state, message(|m| {
+                m.ephemeral();
+                m.content(format!(
+                    "Only the host ({}) or the owner of this server can undo a vote",
+                    game.host.ping()
+                ));
+            })).await.map_err(Into::into);
+        }
+
+        if !game.undo(&state, guild, tally).await {
+            return interaction.respond(&state, message(|m| {
+                m.ephemeral();
+                m.content("There is nothing to undo, only the most recent vote can be undone");
+            })).await.map_err(Into::into);
+        }
+
+        // anything sent after the vote was tallied no longer applies
+        let (vote, ignore) = match &game.state {
+            AvalonState::PartyVote(..) => ("party", "the quest DMs and the next round"),
+            _ => ("quest", "the Lady of the Lake, the Assassin, and the next round"),
+        };
+        interaction.respond(&state, embed(|e| {
+            e.color(Color::GOLD);
+            e.title(format!("The last {} vote has been undone", vote));
+            e.description(format!(
+                "{} undid the vote. Change your reaction to vote again, the vote will be counted \
+                again in 30 seconds if no one does. Ignore any messages about {} that were sent \
+                after this vote.",
+                user.ping(),
+                ignore,
+            ));
+        })).await.map_err(Into::into)
+    }
+}
@@ -6,14 +6,15 @@ use std::sync::Arc;
 use itertools::Itertools;
 use log::error;
 use tokio::sync::Mutex;
-use tokio::time::Duration;
+use tokio::time::{Duration, Instant};
 
 use discorsd::{async_trait, BotState};
 use discorsd::commands::*;
 use discorsd::errors::BotError;
-use discorsd::http::channel::{embed, MessageChannelExt};
+use discorsd::http::channel::{create_message, CreateMessage, embed, MessageChannelExt};
 use discorsd::http::ClientResult;
 use discorsd::http::user::UserExt;
+use discorsd::model::components::ButtonStyle;
 use discorsd::model::emoji::Emoji;
 use discorsd::model::ids::*;
 use discorsd::model::message::{ChannelMessageId, Color, EmbedField};
@@ -22,7 +23,8 @@ use discorsd::shard::dispatch::{ReactionType::*, ReactionUpdate};
 
 use crate::avalon::Avalon;
 use crate::avalon::characters::Loyalty::Evil;
-use crate::avalon::game::{AvalonGame, AvalonState};
+use crate::avalon::game::{AvalonGame, AvalonState, TallyId};
+use crate::avalon::undo::UndoButton;
 use crate::Bot;
 use crate::error::{AvalonError, GameError};
 use crate::utils::ListIterGrammatically;
@@ -190,9 +192,13 @@ impl SlashCommand for VoteStatus {
     }
 }
 
+/// Periodically recount the votes from the reactions on each vote message, in case any reaction
+/// events were missed. Stops once the votes are all in, or once `tally` is no longer the game's most
+/// recent tally (because the vote was counted or undone).
 pub async fn vote_checker<G, F, Fut>(
     state: Arc<BotState<Bot>>,
     guild: GuildId,
+    tally: TallyId,
     yes_no: [char; 2],
     votes_getter: G,
     proceed: F,
@@ -201,13 +207,16 @@ pub async fn vote_checker<G, F, Fut>(
     F: Fn(Arc<BotState<Bot>>, GuildId, Avalon) -> Fut + 'static + Send + Sync,
     Fut: Future<Output=Result<Avalon, (Avalon, BotError<GameError>)>> + 'static + Send,
 {
-    let mut interval = tokio::time::interval(Duration::from_secs(30));
+    const PERIOD: Duration = Duration::from_secs(30);
+    // don't check right away, so that a vote restored by an undo isn't immediately re-tallied from
+    // the same reactions
+    let mut interval = tokio::time::interval_at(Instant::now() + PERIOD, PERIOD);
     loop {
         interval.tick().await;
         let opt = (|| async {
             let mut game_guard = state.bot.avalon_games.write().await;
             let avalon = game_guard.get_mut(&guild)?;
-            let game = avalon.try_game_mut()?;
+            let game = avalon.try_game_mut().filter(|game| game.tally_id() == tally)?;
             let votes = votes_getter(&mut game.state)?;
             let mut all_voted = true;
             for (&(msg, user), vote) in votes {
@@ -255,6 +264,8 @@ pub async fn party_vote_results(
 ) -> Result<Avalon, (Avalon, BotError<GameError>)> {
     // `avalon` is write locked, so it still in the game state
     let game = avalon.game_mut();
+    game.snapshot();
+    let tally = game.tally_id();
     let AvalonGame { state: avalon_state, players, .. } = game;
     if let AvalonState::PartyVote(votes, party) = avalon_state {
         let (approver, rejecter) = votes.iter()
@@ -293,15 +304,18 @@ pub async fn party_vote_results(
                     };
                 }
                 rejects => {
-                    let result = game.channel.send(&state, embed(|e| {
-                        match rejects {
-                            1 => e.title("There is now 1 reject"),
-                            r => e.title(format!("There are now {} rejects in a row", r)),
-                        };
-                        e.fields(vote_summary);
-                        if let Some(board) = board {
-                            e.image(board);
-                        }
+                    let result = game.channel.send(&state, create_message(|m| {
+                        m.embed(|e| {
+                            match rejects {
+                                1 => e.title("There is now 1 reject"),
+                                r => e.title(format!("There are now {} rejects in a row", r)),
+                            };
+                            e.fields(vote_summary);
+                            if let Some(board) = board {
+                                e.image(board);
+                            }
+                        });
+                        undo_button(m, &state, guild, tally);
                     })).await;
                     if let Err(e) = result {
                         return Err((avalon, e.into()));
@@ -318,9 +332,12 @@ pub async fn party_vote_results(
             }
         } else {
             game.rejected_quests = 0;
-            let result = game.channel.send(&state, embed(|e| {
-                e.title("The party has been accepted!");
-                e.fields(vote_summary);
+            let result = game.channel.send(&state, create_message(|m| {
+                m.embed(|e| {
+                    e.title("The party has been accepted!");
+                    e.fields(vote_summary);
+                });
+                undo_button(m, &state, guild, tally);
             })).await;
             if let Err(e) = result {
                 return Err((avalon, e.into()));
@@ -390,6 +407,7 @@ pub async fn party_vote_results(
             tokio::spawn(vote_checker(
                 Arc::clone(&state),
                 guild,
+                tally,
                 [QuestVote::SUCCEED, QuestVote::FAIL],
                 AvalonState::questing_vote_mut,
                 quest_vote_results,
@@ -427,6 +445,8 @@ pub async fn quest_vote_results(
 ) -> Result<Avalon, (Avalon, BotError<GameError>)> {
     // `avalon` is write locked, so it still in the game state
     let game = avalon.game_mut();
+    game.snapshot();
+    let tally = game.tally_id();
     let round = game.round();
     if let AvalonState::Questing(votes) = &mut game.state {
         let fails = votes.iter().filter(|(_, v)| **v == -1).count();
@@ -434,19 +454,22 @@ pub async fn quest_vote_results(
 
         if fails >= round.fails {
             game.good_won.push(false);
-            let result = game.channel.send(&state, embed(|e| {
-                e.color(Color::RED);
-                e.title(format!(
-                    "There {}", if fails == 1 {
-                        "was 1 fail".into()
-                    } else {
-                        format!("were {} fails", fails)
+            let result = game.channel.send(&state, create_message(|m| {
+                m.embed(|e| {
+                    e.color(Color::RED);
+                    e.title(format!(
+                        "There {}", if fails == 1 {
+                            "was 1 fail".into()
+                        } else {
+                            format!("were {} fails", fails)
+                        }
+                    ));
+                    e.description(format!("Reminder: {} were on this quest", questers));
+                    if let Some(board) = game.board_image() {
+                        e.image(board);
                     }
-                ));
-                e.description(format!("Reminder: {} were on this quest", questers));
-                if let Some(board) = game.board_image() {
-                    e.image(board);
-                }
+                });
+                undo_button(m, &state, guild, tally);
             })).await;
             if let Err(e) = result {
                 return Err((avalon, e.into()));
@@ -454,17 +477,20 @@ pub async fn quest_vote_results(
         } else {
             game.good_won.push(true);
             let nvotes = votes.len();
-            let result = game.channel.send(&state, embed(|e| {
-                e.color(Color::BLUE);
-                e.title(if fails == 0 {
-                    format!("All {} were successes", nvotes)
-                } else {
-                    format!("There were {} fails, but {} were required this quest", fails, round.fails)
+            let result = game.channel.send(&state, create_message(|m| {
+                m.embed(|e| {
+                    e.color(Color::BLUE);
+                    e.title(if fails == 0 {
+                        format!("All {} were successes", nvotes)
+                    } else {
+                        format!("There were {} fails, but {} were required this quest", fails, round.fails)
+                    });
+                    e.description(format!("Reminder: {} were on this quest", questers));
+                    if let Some(board) = game.board_image() {
+                        e.image(board);
+                    }
                 });
-                e.description(format!("Reminder: {} were on this quest", questers));
-                if let Some(board) = game.board_image() {
-                    e.image(board);
-                }
+                undo_button(m, &state, guild, tally);
             })).await;
             if let Err(e) = result {
                 return Err((avalon, e.into()));
@@ -487,4 +513,11 @@ pub async fn quest_vote_results(
             );
     }
     Ok(avalon)
-}
\ No newline at end of file
+}
+
+fn undo_button(m: &mut CreateMessage, state: &BotState<Bot>, guild: GuildId, tally: TallyId) {
+    m.button(state, UndoButton(guild, tally), |b| {
+        b.label("Undo");
+        b.style(ButtonStyle::Secondary);
+    });
+}
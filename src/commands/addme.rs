@@ -10,7 +10,7 @@ use discorsd::model::ids::*;
 use discorsd::model::interaction_response::message;
 
 use crate::Bot;
-use crate::error::GameError;
+use crate::error::{AvalonError, GameError};
 use crate::games::GameType;
 
 #[derive(Clone, Debug)]
@@ -75,14 +75,16 @@ async fn avalon(
             interaction.defer(&state).await?
         } else {
             // add player
-            if config.players.len() == 10 {
+            let max_players = config.rules.max_players().unwrap_or_default();
+            if config.players.len() >= max_players {
                 return interaction.respond(&state.client, message(|m| {
-                    m.content("There can be a maximum of 10 people playing Avalon");
+                    m.content(AvalonError::TooManyPlayers(config.players.len() + 1, max_players).to_string());
                     m.ephemeral();
                 })).await;
             }
             if interaction.channel == state.bot.config.channel && user == state.bot.config.owner {
-                for _ in 0..5_usize.saturating_sub(config.players.len()) {
+                let min_players = config.rules.min_players().unwrap_or_default();
+                for _ in 0..min_players.saturating_sub(config.players.len()) {
                     config.players.push(interaction.member().unwrap().clone());
                 };
             } else if let Some(member) = state.cache.member(guild, user).await {
@@ -1,10 +1,8 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use sysinfo::{ComponentExt, Cpu, CpuExt, System, SystemExt};
 
 use command_data_derive::*;
 use discorsd::{async_trait, BotState};
@@ -15,8 +13,7 @@ use discorsd::model::message::Color;
 
 use crate::Bot;
 use crate::error::GameError;
-
-static SYS_INFO: Lazy<Mutex<System>> = Lazy::new(|| Mutex::new(System::new_all()));
+use crate::metrics;
 
 #[derive(Debug, Copy, Clone)]
 pub struct SysInfoCommand;
@@ -44,63 +41,63 @@ impl SlashCommand for SysInfoCommand {
             e.color(Color::from_rgb(0x2E, 0x8B, 0xC0));
         });
 
-        // wrong
-        #[allow(clippy::significant_drop_tightening)]
-        {
-            let mut sys = SYS_INFO.lock().unwrap();
-            sys.refresh_all();
-
-            if data.0.iter().any(|it| matches!(it, Choices::Cpu | Choices::All)) {
-                let cpus = sys.cpus();
-                let value = if cpus.is_empty() {
-                    "No CPUs found".to_owned()
+        let snapshot = metrics::snapshot();
+        if data.0.iter().any(|it| matches!(it, Choices::Cpu | Choices::All)) {
+            let cpus = &snapshot.cpus;
+            let value = if cpus.is_empty() {
+                "No CPUs found".to_owned()
+            } else {
+                let value = cpus.iter()
+                    .map(|(name, usage)| format!("{}: {:.2}%", name, usage))
+                    .join("\n");
+                if cpus.len() == 1 {
+                    value
                 } else {
-                    let value = cpus.iter()
-                        .map(|cpu| format!("{}: {:.2}%", cpu.name(), cpu.cpu_usage()))
-                        .join("\n");
-                    if cpus.len() == 1 {
-                        value
-                    } else {
-                        let avg: f32 = cpus.iter()
-                            .map(Cpu::cpu_usage)
-                            .sum();
-                        format!("```{}\nAverage: {:.2}%```", value, avg / cpus.len() as f32)
-                    }
-                };
-                embed.field(("CPU Usage", value));
-            }
-            if data.0.iter().any(|it| matches!(it, Choices::Memory | Choices::All)) {
-                let used = sys.used_memory();
-                let total = sys.total_memory();
-                let string = format!(
+                    let avg: f32 = cpus.iter()
+                        .map(|(_, usage)| usage)
+                        .sum();
+                    format!("```{}\nAverage: {:.2}%```", value, avg / cpus.len() as f32)
+                }
+            };
+            embed.field(("CPU Usage", value));
+        }
+        if data.0.iter().any(|it| matches!(it, Choices::Memory | Choices::All)) {
+            // sysinfo reports memory in bytes
+            const MB: u64 = 1024 * 1024;
+            let used = snapshot.used_memory;
+            let total = snapshot.total_memory;
+            let string = if total == 0 {
+                "Memory usage is unavailable on this computer".to_owned()
+            } else {
+                format!(
                     "```    {:.2}%\n\
                      Used : {} MB\n\
                      Total: {} MB```",
                     (used as f64) / (total as f64) * 100.0,
-                    used / 1024,
-                    total / 1024
-                );
-                embed.field(("Memory Usage", string));
-            }
-            if data.0.iter().any(|it| matches!(it, Choices::Temperature | Choices::All)) {
-                let components = sys.components();
-                let value = if components.is_empty() {
-                    "No components found".to_owned()
+                    used / MB,
+                    total / MB
+                )
+            };
+            embed.field(("Memory Usage", string));
+        }
+        if data.0.iter().any(|it| matches!(it, Choices::Temperature | Choices::All)) {
+            let temperatures = &snapshot.temperatures;
+            let value = if temperatures.is_empty() {
+                "Temperature sensors are unavailable on this computer".to_owned()
+            } else {
+                let value = temperatures.iter()
+                    .map(|(label, temp)| format!("{}: {:.2} °C", label, temp))
+                    .join("\n");
+                if temperatures.len() == 1 {
+                    value
                 } else {
-                    let value = components.iter()
-                        .map(|c| format!("{}: {:.2} °C", c.label(), c.temperature()))
-                        .join("\n");
-                    if components.len() == 1 {
-                        value
-                    } else {
-                        let avg: f32 = components.iter()
-                            .map(ComponentExt::temperature)
-                            .sum();
-                        format!("```{}\nAverage: {:.2} °C```", value, avg / components.len() as f32)
-                    }
-                };
-                embed.field(("Component Temperature", value));
-            }
+                    let avg: f32 = temperatures.iter()
+                        .map(|(_, temp)| temp)
+                        .sum();
+                    format!("```{}\nAverage: {:.2} °C```", value, avg / temperatures.len() as f32)
+                }
+            };
+            embed.field(("Component Temperature", value));
         }
 
         interaction.respond(state, embed).await.map_err(Into::into)
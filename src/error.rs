@@ -31,17 +31,21 @@ bot_error_from!(HangmanError => E = GameError);
 
 #[derive(Error, Debug)]
 pub enum AvalonError {
-    TooManyPlayers(usize),
+    TooManyPlayers(usize, usize),
     Stopped,
     NotVoting,
+    InvalidRules(usize, &'static str),
+    InvalidPlayerCount(i64),
 }
 
 impl Display for AvalonError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::TooManyPlayers(n) => write!(f, "Too many players! {n} is more than the maximum number of players (10)."),
+            Self::TooManyPlayers(n, max) => write!(f, "Too many players! {n} is more than the maximum number of players ({max})."),
             Self::Stopped => f.write_str("Game Already Over"),
             Self::NotVoting => f.write_str("No longer in the voting phase"),
+            Self::InvalidRules(n, reason) => write!(f, "Invalid rules for {n} players: {reason}"),
+            Self::InvalidPlayerCount(n) => write!(f, "{n} is not a valid number of players"),
         }
     }
 }
@@ -77,6 +77,7 @@ mod coup;
 pub mod utils;
 pub mod games;
 pub mod error;
+pub mod metrics;
 
 #[derive(Deserialize)]
 pub struct Config {
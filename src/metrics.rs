@@ -0,0 +1,59 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use sysinfo::{ComponentExt, CpuExt, System, SystemExt};
+
+/// How long a reading is reused before the OS is asked again
+const MAX_AGE: Duration = Duration::from_secs(5);
+
+static METRICS: Lazy<Mutex<Metrics>> = Lazy::new(|| Mutex::new(Metrics {
+    sys: System::new_all(),
+    last: None,
+}));
+
+struct Metrics {
+    sys: System,
+    last: Option<(Instant, Snapshot)>,
+}
+
+/// A reading of the CPU, memory, and temperatures of the computer running this bot
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Each CPU's name and usage, in percent
+    pub cpus: Vec<(String, f32)>,
+    pub used_memory: u64,
+    pub total_memory: u64,
+    /// Each component's label and temperature, in °C. Empty if there are no sensors that can be
+    /// read.
+    pub temperatures: Vec<(String, f32)>,
+}
+
+/// The current system metrics, reusing the last reading if it is less than [`MAX_AGE`] old.
+pub fn snapshot() -> Snapshot {
+    let mut metrics = METRICS.lock().unwrap();
+    if let Some((taken, snapshot)) = &metrics.last {
+        if taken.elapsed() < MAX_AGE {
+            return snapshot.clone();
+        }
+    }
+
+    let sys = &mut metrics.sys;
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    sys.refresh_components();
+    let snapshot = Snapshot {
+        cpus: sys.cpus().iter()
+            .map(|cpu| (cpu.name().to_owned(), cpu.cpu_usage()))
+            .collect(),
+        used_memory: sys.used_memory(),
+        total_memory: sys.total_memory(),
+        // sensors that can't be read report NaN
+        temperatures: sys.components().iter()
+            .filter(|c| c.temperature().is_finite())
+            .map(|c| (c.label().to_owned(), c.temperature()))
+            .collect(),
+    };
+    metrics.last = Some((Instant::now(), snapshot.clone()));
+    snapshot
+}